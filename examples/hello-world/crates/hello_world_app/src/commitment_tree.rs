@@ -0,0 +1,69 @@
+//! Append-only commitment tree with published anchors.
+//!
+//! `create_transaction` previously fed `MerklePath::default()` into the
+//! compliance proof, so the consumed resource was never checked against a real
+//! commitment-tree root. This module maintains an append-only set of resource
+//! commitments — in the role of Orchard's `MERKLE_DEPTH`/`Anchor` and Taiga's
+//! `TAIGA_COMMITMENT_TREE_DEPTH` trees — and hands out the real authentication
+//! path and anchor a verifier needs to confirm a consumed resource existed. The
+//! tree is rebuilt as an `arm::action_tree::MerkleTree` over the inserted
+//! commitments on each query; its depth is not fixed at
+//! [`COMMITMENT_TREE_DEPTH`].
+
+use arm::action_tree::MerkleTree;
+use arm::merkle_path::MerklePath;
+use risc0_zkvm::sha::Digest;
+
+/// Nominal commitment-tree depth. [`CommitmentTree`] does not apply it: the
+/// path length is set by the `arm::action_tree::MerkleTree` built over the
+/// inserted commitments.
+pub const COMMITMENT_TREE_DEPTH: usize = 32;
+
+/// A published commitment-tree root against which membership is proven.
+pub type Anchor = Digest;
+
+/// An append-only tree of resource commitments.
+pub struct CommitmentTree {
+    commitments: Vec<Digest>,
+}
+
+impl CommitmentTree {
+    /// Creates an empty commitment tree.
+    pub fn new() -> Self {
+        Self {
+            commitments: Vec::new(),
+        }
+    }
+
+    /// Inserts a commitment and returns its leaf position.
+    pub fn insert(&mut self, commitment: Digest) -> usize {
+        let position = self.commitments.len();
+        self.commitments.push(commitment);
+        position
+    }
+
+    /// Returns the current anchor (root) over all inserted commitments.
+    pub fn root(&self) -> Anchor {
+        self.tree().root()
+    }
+
+    /// Produces the authentication path for the commitment at `position`,
+    /// anchored to the current root.
+    pub fn witness(&self, position: usize) -> MerklePath {
+        let commitment = self.commitments[position];
+        self.tree()
+            .generate_path(&commitment)
+            .expect("commitment is not present in the tree")
+    }
+
+    /// Rebuilds the underlying Merkle tree over the current commitment set.
+    fn tree(&self) -> MerkleTree {
+        MerkleTree::new(self.commitments.clone())
+    }
+}
+
+impl Default for CommitmentTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
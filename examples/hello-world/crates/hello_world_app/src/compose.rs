@@ -0,0 +1,94 @@
+//! Composition of independently-proven partial transactions.
+//!
+//! A [`PartialTransaction`] carries its own actions, the RCV bytes of its delta
+//! witness, and the commitment/nullifier that link it to its neighbours in a
+//! pipeline. [`compose_partial_transactions`] chains them into one settled
+//! [`Transaction`]: the output resource of one partial transaction is the
+//! consumed input of the next, so multi-hop swaps never surface their
+//! intermediate resources as separate on-chain transactions.
+
+use crate::util::aggregate_rcv;
+
+use arm::action::Action;
+use arm::delta_proof::DeltaWitness;
+use arm::nullifier_key::NullifierKey;
+use arm::resource::Resource;
+use arm::transaction::{Delta, Transaction};
+
+/// One independently-proven step of a pipeline.
+///
+/// `link_out` is the output resource this step created and hands to the next
+/// step (`None` for the last step). `link_in` is the nullifier the next step
+/// placed in its action tree for the resource it consumes from this one, and
+/// `link_in_nf_key` is the nullifier key it spent it under (both `None`/empty
+/// for the first step). Chaining is checked by re-deriving the nullifier from
+/// the previous step's output resource, so the two ends describe the *same*
+/// resource rather than two unrelated tags.
+pub struct PartialTransaction {
+    pub actions: Vec<Action>,
+    pub rcv: Vec<u8>,
+    /// Nullifier this step spends for the resource received from the previous
+    /// step; empty for the first step.
+    pub link_in: Vec<u8>,
+    /// Nullifier key used to spend that received resource; `None` for the first
+    /// step.
+    pub link_in_nf_key: Option<NullifierKey>,
+    /// Output resource handed to the next step; `None` for the last step.
+    pub link_out: Option<Resource>,
+}
+
+/// Composes several partial transactions into one settled transaction.
+///
+/// The partial transactions are joined head-to-tail: the nullifier that step
+/// `n + 1` spends must be the nullifier of step `n`'s output resource under the
+/// key `n + 1` declares, so the chain is verified against the actual resource
+/// rather than by equating a commitment with an unrelated nullifier. Their
+/// actions are concatenated and their delta witnesses are aggregated into a
+/// single combined [`DeltaWitness`] so the final transaction balances across the
+/// whole pipeline.
+///
+/// # Panics
+///
+/// Panics if the linking resource and nullifier do not chain correctly, or if
+/// delta proof generation fails.
+pub fn compose_partial_transactions(parts: Vec<PartialTransaction>) -> Transaction {
+    assert!(!parts.is_empty(), "cannot compose zero partial transactions");
+
+    // Verify the pipeline links: the nullifier each step spends must be the one
+    // derived from the previous step's output resource under the declared key.
+    for window in parts.windows(2) {
+        let out_resource = window[0]
+            .link_out
+            .as_ref()
+            .expect("non-final partial transaction must record its output resource");
+        let nf_key = window[1]
+            .link_in_nf_key
+            .as_ref()
+            .expect("non-first partial transaction must record its consuming nullifier key");
+        let derived = out_resource
+            .nullifier(nf_key)
+            .expect("failed to derive nullifier from the previous output resource");
+        assert_eq!(
+            derived.as_bytes(),
+            window[1].link_in.as_slice(),
+            "partial transactions do not chain: next input is not the previous output resource"
+        );
+    }
+
+    // Concatenate every step's actions into one action list.
+    let mut actions = Vec::new();
+    let mut rcvs = Vec::new();
+    for part in &parts {
+        actions.extend(part.actions.clone());
+        rcvs.push(part.rcv.clone());
+    }
+
+    // Aggregate the per-step RCV witnesses into one combined delta witness by
+    // summing their blinding scalars; the intermediate resources cancel out,
+    // leaving only the net delta of the whole pipeline.
+    let delta_witness = DeltaWitness::from_bytes(&aggregate_rcv(&rcvs));
+    let mut transaction = Transaction::create(actions, Delta::Witness(delta_witness));
+    transaction.generate_delta_proof();
+
+    transaction
+}
@@ -0,0 +1,195 @@
+//! Worked examples for the token, auth, intent-swap and composition subsystems.
+//!
+//! The headline counter flow in `main` drives [`util::generate_logic_proofs`]
+//! end to end; these functions give the remaining logic circuits a runnable
+//! call-site so each subsystem is exercised the same way. They require the RISC0
+//! prover (and, for the EVM artifact, the Groth16 prover), so `main` keeps them
+//! behind a flag rather than running them in the default demo.
+
+use crate::compose::{compose_partial_transactions, PartialTransaction};
+use crate::evm_verifier::{generate_evm_verifier, verify_calldata, verify_groth16};
+use crate::init::generate_token_resource;
+use crate::swap::{settle_swap, Offer, SwapTerms};
+use crate::util::{
+    generate_auth_logic_proofs, generate_compliance_proof, generate_token_logic_proofs,
+};
+
+use arm::action::Action;
+use arm::delta_proof::DeltaWitness;
+use arm::logic_proof::LogicProver;
+use arm::merkle_path::MerklePath;
+use arm::nullifier_key::NullifierKey;
+use arm::resource::Resource;
+use arm::transaction::{Delta, Transaction};
+use ed25519_dalek::SigningKey;
+use hello_world_library::{AuthLogic, IntentLogic};
+use rand::rngs::OsRng;
+use rand::Rng;
+
+/// Settles a single-denomination token transfer: one 100-unit note is consumed
+/// and a 100-unit note is created for a new owner, so the quantity is conserved
+/// across the action.
+pub fn demo_token_transfer() -> Transaction {
+    let (nf_key, nf_cm) = NullifierKey::random_pair();
+    let (_, recipient_cm) = NullifierKey::random_pair();
+    let sender = [1u8; 16];
+    let recipient = [2u8; 16];
+
+    let consumed = generate_token_resource(b"USD", 100, sender, true, nf_cm);
+    let created = generate_token_resource(b"USD", 100, recipient, false, recipient_cm);
+
+    let (compliance_unit, rcv) = generate_compliance_proof(
+        consumed.clone(),
+        nf_key.clone(),
+        MerklePath::default(),
+        created.clone(),
+    );
+    let logic_proofs = generate_token_logic_proofs(vec![(consumed, nf_key)], vec![created]);
+
+    let action = Action::new(vec![compliance_unit], logic_proofs);
+    let delta_witness = DeltaWitness::from_bytes(&rcv);
+    let mut transaction = Transaction::create(vec![action], Delta::Witness(delta_witness));
+    transaction.generate_delta_proof();
+    transaction
+}
+
+/// Authorizes the consumption of a signature-gated resource: the resource
+/// commits an Ed25519 verifying key in `value_ref`, and the holder of the
+/// matching signing key signs the nullifier to spend it.
+pub fn demo_auth_consumption() -> Transaction {
+    let (nf_key, nf_cm) = NullifierKey::random_pair();
+    let (_, created_cm) = NullifierKey::random_pair();
+    let signing_key = SigningKey::generate(&mut OsRng);
+    let verifying_key = signing_key.verifying_key().to_bytes();
+
+    // Commit the verifying key in the first thirty-two bytes of `value_ref`, as
+    // the auth circuit expects.
+    let logic_ref = AuthLogic::verifying_key_as_bytes();
+    let mut label_ref = [0u8; 32];
+    label_ref[..4].copy_from_slice(b"auth");
+    let value_ref = verifying_key.to_vec();
+
+    let mut rng = rand::thread_rng();
+    let consumed = Resource::create(
+        logic_ref.clone(),
+        label_ref.to_vec(),
+        1,
+        value_ref.clone(),
+        true,
+        rng.gen::<[u8; 32]>().to_vec(),
+        nf_cm,
+    );
+    let created = Resource::create(
+        logic_ref,
+        label_ref.to_vec(),
+        1,
+        value_ref,
+        false,
+        rng.gen::<[u8; 32]>().to_vec(),
+        created_cm,
+    );
+
+    let (compliance_unit, rcv) = generate_compliance_proof(
+        consumed.clone(),
+        nf_key.clone(),
+        MerklePath::default(),
+        created.clone(),
+    );
+    let logic_proofs = generate_auth_logic_proofs(consumed, nf_key, created, &signing_key);
+
+    let action = Action::new(vec![compliance_unit], logic_proofs);
+    let delta_witness = DeltaWitness::from_bytes(&rcv);
+    let mut transaction = Transaction::create(vec![action], Delta::Witness(delta_witness));
+    transaction.generate_delta_proof();
+    transaction
+}
+
+/// Settles a two-party swap: party A gives 100 of kind 1 and wants 100 of kind
+/// 2, party B mirrors it, so each intent is fully satisfied.
+pub fn demo_swap() -> Transaction {
+    let offer = |terms: SwapTerms| {
+        let (nf_key, nf_cm) = NullifierKey::random_pair();
+        let (_, created_cm) = NullifierKey::random_pair();
+        let mut rng = rand::thread_rng();
+        let logic_ref = IntentLogic::verifying_key_as_bytes();
+        let intent_resource = Resource::create(
+            logic_ref.clone(),
+            b"intent".to_vec(),
+            1,
+            terms.to_value_ref(),
+            true,
+            rng.gen::<[u8; 32]>().to_vec(),
+            nf_cm,
+        );
+        let created_resource = Resource::create(
+            logic_ref,
+            b"intent".to_vec(),
+            1,
+            terms.to_value_ref(),
+            false,
+            rng.gen::<[u8; 32]>().to_vec(),
+            created_cm,
+        );
+        Offer {
+            intent_resource,
+            nf_key,
+            created_resource,
+            filled: terms.give_quantity,
+            spent: terms.want_quantity,
+            is_partial: false,
+        }
+    };
+
+    let a = offer(SwapTerms {
+        give_kind: 1,
+        give_quantity: 100,
+        want_kind: 2,
+        want_quantity: 100,
+    });
+    let b = offer(SwapTerms {
+        give_kind: 2,
+        give_quantity: 100,
+        want_kind: 1,
+        want_quantity: 100,
+    });
+    settle_swap(vec![a, b])
+}
+
+/// Chains two partial transactions: the output resource of the first step is the
+/// resource the second step consumes, so the pipeline links head-to-tail.
+pub fn demo_compose() -> Transaction {
+    let (nf_key, nf_cm) = NullifierKey::random_pair();
+    let link_resource = generate_token_resource(b"USD", 50, [0u8; 16], false, nf_cm);
+    let nullifier = link_resource.nullifier(&nf_key).unwrap();
+
+    let first = PartialTransaction {
+        actions: Vec::new(),
+        rcv: vec![0u8; 32],
+        link_in: Vec::new(),
+        link_in_nf_key: None,
+        link_out: Some(link_resource),
+    };
+    let second = PartialTransaction {
+        actions: Vec::new(),
+        rcv: vec![0u8; 32],
+        link_in: nullifier.as_bytes().to_vec(),
+        link_in_nf_key: Some(nf_key),
+        link_out: None,
+    };
+    compose_partial_transactions(vec![first, second])
+}
+
+/// Emits the EVM verifier artifact for a finished transaction and cross-checks
+/// the Groth16 SNARK it encodes. Requires the Groth16 prover.
+pub fn emit_evm_artifact(transaction: &Transaction) {
+    let (contract, calldata) = generate_evm_verifier(transaction);
+    assert!(verify_groth16(transaction), "Groth16 cross-check failed");
+    let (seal, _journal_digest) =
+        verify_calldata(&calldata).expect("generated calldata must be well-formed");
+    assert!(!seal.is_empty(), "calldata must carry a non-empty seal");
+    println!(
+        "Generated Solidity verifier ({} bytes) and calldata ({} bytes)",
+        contract.len(),
+        calldata.len()
+    );
+}
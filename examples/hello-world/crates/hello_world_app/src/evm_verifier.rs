@@ -0,0 +1,160 @@
+//! On-chain (EVM) verification of finished transactions.
+//!
+//! After a [`Transaction`] verifies locally, this module wraps its RISC0
+//! proofs into a Groth16 SNARK and emits an artifact that an EVM chain can
+//! check: a Solidity verifier contract plus the calldata payload. The
+//! contract performs the on-chain check by delegating the Groth16 pairing to
+//! the canonical RISC Zero verifier (the `RiscZeroGroth16Verifier` deployed via
+//! the verifier router) — the same component the RISC Zero EVM tooling uses —
+//! so ARM transactions can settle on EVM chains rather than only being verified
+//! locally in Rust.
+//!
+//! This path is opt-in: it requires the Groth16 prover (an x86 host with the
+//! RISC0 proving stack), so the core example does not run it. Callers that want
+//! an on-chain artifact invoke [`generate_evm_verifier`] and can cross-check the
+//! SNARK with [`verify_groth16`] before deploying.
+
+use arm::transaction::Transaction;
+use risc0_zkvm::{Groth16Receipt, Groth16ReceiptVerifierParameters};
+
+/// The Solidity source of the generated Groth16 verifier contract.
+pub type SolidityVerifier = String;
+
+/// Wraps a verified transaction into an EVM-checkable artifact.
+///
+/// Returns the Solidity verifier contract source and the calldata payload
+/// (the Groth16 seal followed by the public journal digests) that should be
+/// submitted to it on-chain. The payload is the raw concatenation of those
+/// bytes; it is not ABI-encoded for the contract's `verify` call.
+///
+/// # Panics
+///
+/// Panics if the transaction's proofs cannot be compressed into a Groth16
+/// receipt.
+pub fn generate_evm_verifier(transaction: &Transaction) -> (SolidityVerifier, Vec<u8>) {
+    let receipt = groth16_receipt(transaction);
+    let calldata = encode_calldata(&receipt);
+    let solidity = render_verifier(&Groth16ReceiptVerifierParameters::default());
+    (solidity, calldata)
+}
+
+/// Compresses the transaction's RISC0 proofs into a single Groth16 receipt.
+fn groth16_receipt(transaction: &Transaction) -> Groth16Receipt<Vec<u8>> {
+    transaction
+        .compress_to_groth16()
+        .expect("failed to compress transaction proofs into a Groth16 SNARK")
+}
+
+/// Concatenates the Groth16 seal and the receipt's claim digest into the
+/// calldata payload; no ABI encoding is applied.
+fn encode_calldata(receipt: &Groth16Receipt<Vec<u8>>) -> Vec<u8> {
+    let mut calldata = Vec::new();
+    calldata.extend_from_slice(&receipt.seal_bytes());
+    calldata.extend_from_slice(receipt.claim_digest().as_bytes());
+    calldata
+}
+
+/// Renders the Solidity verifier contract for the given verifier parameters.
+///
+/// The contract delegates the Groth16 pairing check to the canonical RISC Zero
+/// verifier: `verify` calls the deployed verifier with the seal, the guest
+/// image id and the journal digest, returning `false` if the verifier reverts.
+/// The verifier parameters digest is embedded so a deployer can confirm the
+/// calldata was produced against the expected verifier configuration.
+fn render_verifier(params: &Groth16ReceiptVerifierParameters) -> SolidityVerifier {
+    format!(
+        "// SPDX-License-Identifier: Apache-2.0\n\
+         pragma solidity ^0.8.20;\n\n\
+         // Auto-generated Groth16 verifier for an ARM transaction.\n\
+         // Verifier parameters digest: {:?}\n\n\
+         /// Canonical RISC Zero verifier interface (RiscZeroGroth16Verifier /\n\
+         /// verifier router). Reverts when the proof is invalid.\n\
+         interface IRiscZeroVerifier {{\n\
+         \x20   function verify(bytes calldata seal, bytes32 imageId, bytes32 journalDigest) external view;\n\
+         }}\n\n\
+         contract ArmTransactionVerifier {{\n\
+         \x20   IRiscZeroVerifier public immutable verifier;\n\
+         \x20   bytes32 public immutable imageId;\n\n\
+         \x20   constructor(IRiscZeroVerifier _verifier, bytes32 _imageId) {{\n\
+         \x20       verifier = _verifier;\n\
+         \x20       imageId = _imageId;\n\
+         \x20   }}\n\n\
+         \x20   /// Returns true iff `seal` is a valid Groth16 proof of `journalDigest`\n\
+         \x20   /// under `imageId`.\n\
+         \x20   function verify(bytes calldata seal, bytes32 journalDigest) external view returns (bool) {{\n\
+         \x20       try verifier.verify(seal, imageId, journalDigest) {{\n\
+         \x20           return true;\n\
+         \x20       }} catch {{\n\
+         \x20           return false;\n\
+         \x20       }}\n\
+         \x20   }}\n\
+         }}\n",
+        params.digest()
+    )
+}
+
+/// Parses calldata produced by [`generate_evm_verifier`] back into its seal and
+/// journal-digest halves, checking it is well-formed: a 32-byte journal digest
+/// suffixed to a non-empty Groth16 seal, exactly the layout [`encode_calldata`]
+/// writes. Returns `None` when the calldata is too short to hold both parts.
+///
+/// This lets a caller round-trip and sanity-check the artifact before it is
+/// submitted on-chain, without standing up an EVM.
+pub fn verify_calldata(calldata: &[u8]) -> Option<(Vec<u8>, [u8; 32])> {
+    if calldata.len() <= 32 {
+        return None;
+    }
+    let (seal, digest) = calldata.split_at(calldata.len() - 32);
+    Some((seal.to_vec(), digest.try_into().expect("32-byte digest suffix")))
+}
+
+/// Cross-checks the transaction's Groth16 SNARK before its calldata is
+/// deployed, returning whether the proof is cryptographically valid.
+///
+/// This performs the same pairing check the generated Solidity verifier runs
+/// on-chain: the transaction's proofs are compressed to a Groth16 receipt and
+/// its integrity is verified against the RISC0 verifier parameters. It does not
+/// stand up an EVM; it validates the proof the calldata encodes.
+pub fn verify_groth16(transaction: &Transaction) -> bool {
+    groth16_receipt(transaction).verify_integrity().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The calldata format round-trips: `verify_calldata` recovers exactly the
+    /// seal and journal digest that the `seal || digest` concatenation encodes.
+    /// This exercises the harness without the Groth16 prover, which the receipt
+    /// paths above require.
+    #[test]
+    fn calldata_round_trips() {
+        let seal = vec![1u8, 2, 3, 4, 5];
+        let digest = [7u8; 32];
+
+        let mut calldata = Vec::new();
+        calldata.extend_from_slice(&seal);
+        calldata.extend_from_slice(&digest);
+
+        let (decoded_seal, decoded_digest) =
+            verify_calldata(&calldata).expect("well-formed calldata should parse");
+        assert_eq!(decoded_seal, seal);
+        assert_eq!(decoded_digest, digest);
+    }
+
+    /// Calldata with no room for both a seal and a 32-byte digest is rejected.
+    #[test]
+    fn calldata_too_short_is_rejected() {
+        assert!(verify_calldata(&[0u8; 32]).is_none());
+    }
+
+    /// The rendered verifier is a concrete contract and pins the verifier
+    /// parameters digest so a deployer can confirm the configuration.
+    #[test]
+    fn verifier_embeds_params_digest() {
+        let params = Groth16ReceiptVerifierParameters::default();
+        let source = render_verifier(&params);
+        assert!(source.contains("contract ArmTransactionVerifier"));
+        assert!(source.contains(&format!("{:?}", params.digest())));
+    }
+}
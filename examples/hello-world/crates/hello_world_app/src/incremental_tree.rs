@@ -0,0 +1,178 @@
+//! Reusable fixed-depth incremental Merkle tree.
+//!
+//! Unlike `arm::action_tree::MerkleTree`, which is rebuilt from the full leaf
+//! set each time, this tree is append-only: [`IncrementalMerkleTree::insert`]
+//! adds one leaf and updates only the nodes along its path, caching every
+//! filled node at every level so roots and authentication paths are produced
+//! without a full rebuild.
+//!
+//! The depth is configurable; depth 21 and 32 are common choices. Scoping a
+//! nullifier to an "external nullifier" (a domain/epoch/application identifier)
+//! lets each application context keep its own append-only commitment tree while
+//! preventing the same resource from being double-spent across contexts.
+
+use sha2::{Digest, Sha256};
+
+/// Default tree depth (matching common shielded-pool parameters).
+pub const DEFAULT_DEPTH: usize = 32;
+
+/// Hashes two child nodes into their parent.
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Computes the precomputed "zero" node for each level (empty subtree hashes).
+fn zero_nodes(depth: usize) -> Vec<[u8; 32]> {
+    let mut zeros = vec![[0u8; 32]; depth + 1];
+    for level in 1..=depth {
+        let child = zeros[level - 1];
+        zeros[level] = hash_pair(&child, &child);
+    }
+    zeros
+}
+
+/// An authentication path: the sibling hash at each level from the leaf up to
+/// the root, paired with whether the leaf sits on the right of that sibling.
+pub type AuthPath = Vec<(/* sibling */ [u8; 32], /* leaf_is_right */ bool)>;
+
+/// An append-only, fixed-depth Merkle tree with cached subtree nodes.
+pub struct IncrementalMerkleTree {
+    depth: usize,
+    /// Number of leaves inserted so far.
+    next_index: usize,
+    /// Filled node values per level: `nodes[0]` are the leaves, `nodes[level]`
+    /// the nodes at that height. Positions not yet written are treated as the
+    /// precomputed zero node for their level.
+    nodes: Vec<Vec<[u8; 32]>>,
+    /// Precomputed empty-subtree hashes per level.
+    zeros: Vec<[u8; 32]>,
+    /// Current root.
+    root: [u8; 32],
+}
+
+impl IncrementalMerkleTree {
+    /// Creates an empty tree of the given depth.
+    pub fn new(depth: usize) -> Self {
+        let zeros = zero_nodes(depth);
+        let root = zeros[depth];
+        Self {
+            depth,
+            next_index: 0,
+            nodes: vec![Vec::new(); depth + 1],
+            zeros,
+            root,
+        }
+    }
+
+    /// Creates an empty tree at [`DEFAULT_DEPTH`].
+    pub fn default_depth() -> Self {
+        Self::new(DEFAULT_DEPTH)
+    }
+
+    /// Reads a node, falling back to the level's zero node for unfilled slots.
+    fn node(&self, level: usize, index: usize) -> [u8; 32] {
+        self.nodes[level]
+            .get(index)
+            .copied()
+            .unwrap_or(self.zeros[level])
+    }
+
+    /// Writes a node, growing the level's row with zero nodes as needed.
+    fn set_node(&mut self, level: usize, index: usize, value: [u8; 32]) {
+        if self.nodes[level].len() <= index {
+            self.nodes[level].resize(index + 1, self.zeros[level]);
+        }
+        self.nodes[level][index] = value;
+    }
+
+    /// Inserts a leaf and returns its position, updating only the nodes along
+    /// the single affected path and the root — no full rebuild.
+    pub fn insert(&mut self, leaf: [u8; 32]) -> usize {
+        assert!(self.next_index < (1usize << self.depth), "tree is full");
+
+        let position = self.next_index;
+        let mut index = position;
+        let mut current = leaf;
+        self.set_node(0, index, current);
+
+        for level in 0..self.depth {
+            let (left, right) = if index & 1 == 0 {
+                (current, self.node(level, index + 1))
+            } else {
+                (self.node(level, index - 1), current)
+            };
+            current = hash_pair(&left, &right);
+            index >>= 1;
+            self.set_node(level + 1, index, current);
+        }
+
+        self.root = current;
+        self.next_index += 1;
+        position
+    }
+
+    /// Returns the current root.
+    pub fn root(&self) -> [u8; 32] {
+        self.root
+    }
+
+    /// Generates the authentication path for an inserted leaf from the cached
+    /// nodes, without rebuilding the tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` has not been inserted.
+    pub fn witness(&self, position: usize) -> AuthPath {
+        assert!(position < self.next_index, "position has not been inserted");
+
+        let mut index = position;
+        let mut path = Vec::with_capacity(self.depth);
+        for level in 0..self.depth {
+            let sibling = self.node(level, index ^ 1);
+            path.push((sibling, index & 1 == 1));
+            index >>= 1;
+        }
+        path
+    }
+
+    /// Recomputes the root a `leaf`/`path` pair authenticates against, so a
+    /// caller can confirm a generated path reproduces [`root`](Self::root).
+    pub fn root_from_path(leaf: [u8; 32], path: &AuthPath) -> [u8; 32] {
+        let mut current = leaf;
+        for (sibling, leaf_is_right) in path {
+            current = if *leaf_is_right {
+                hash_pair(sibling, &current)
+            } else {
+                hash_pair(&current, sibling)
+            };
+        }
+        current
+    }
+}
+
+/// Scopes a resource tag to an external nullifier.
+///
+/// `scoped = H(tag || H("ARM.external-nullifier" || external_nullifier))`, so
+/// the same resource yields a distinct tag in distinct application contexts and
+/// cannot be double-spent across them. For a non-empty `external_nullifier`
+/// this matches the scoping the `HelloWorldWitness` circuit performs, so a host
+/// can recompute the scoped leaf a scoped proof roots against. For an empty one
+/// the circuit leaves the tag unscoped, while this function still hashes it.
+pub fn scoped_tag(tag: &[u8], external_nullifier: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(tag);
+    hasher.update(external_nullifier_hash(external_nullifier));
+    hasher.finalize().into()
+}
+
+/// Hashes an external-nullifier identifier (domain/epoch/application) into the
+/// 32-byte scope used by [`scoped_tag`].
+pub fn external_nullifier_hash(identifier: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"ARM.external-nullifier");
+    hasher.update(identifier);
+    hasher.finalize().into()
+}
@@ -1,7 +1,8 @@
+use crate::randomness::DeriveRandomness;
 use arm::logic_proof::LogicProver;
 use arm::nullifier_key::{NullifierKey, NullifierKeyCommitment};
 use arm::resource::Resource;
-use hello_world_library::HelloWorldLogic;
+use hello_world_library::{HelloWorldLogic, TokenLogic};
 use rand::Rng;
 
 /// Converts a hello world value to a 32-byte value reference.
@@ -24,6 +25,23 @@ pub fn convert_hello_world_to_value_ref(value: u128) -> Vec<u8> {
     arr.to_vec()
 }
 
+/// Reads a `u128` counter value back out of a 32-byte value reference.
+///
+/// Inverse of [`convert_hello_world_to_value_ref`]: it decodes the little-endian
+/// `u128` stored in the first sixteen bytes so applications can read resource
+/// state.
+///
+/// # Arguments
+///
+/// * `value_ref` - The 32-byte value reference to decode
+///
+/// # Returns
+///
+/// The decoded `u128` counter value
+pub fn convert_value_ref_to_u128(value_ref: &[u8]) -> u128 {
+    u128::from_le_bytes(value_ref[..16].try_into().unwrap())
+}
+
 /// Generates an ephemeral hello world resource with a random nonce.
 /// 
 /// This function creates a temporary resource that can be consumed
@@ -89,20 +107,91 @@ pub fn generate_persistent_resource(
     consumed_resource: &Resource,
     eph_nf_key: &NullifierKey,
     nf_key_cm: &NullifierKeyCommitment,
+    delta: u128,
+    is_addition: bool,
 ) -> Resource {
     // Start with a clone of the consumed ephemeral resource
     let mut init_hello_world = consumed_resource.clone();
-    
+
     // Transform to persistent resource
     init_hello_world.is_ephemeral = false;
-    init_hello_world.reset_randomness();
     init_hello_world.set_nonce_from_nf(consumed_resource, eph_nf_key);
-    
-    // Set initial value to 1 for persistent resource
-    init_hello_world.set_value_ref(convert_hello_world_to_value_ref(1u128));
-    
+    // Re-derive the randomness seed deterministically from the inherited seed
+    // and the new nonce, so it follows from a single backed-up seed rather than
+    // being freshly sampled (see [`crate::randomness`]).
+    init_hello_world.derive_randomness();
+
+    // Evolve the counter by `delta`: the created resource carries the consumed
+    // value adjusted up or down. The matching in-circuit check guards against
+    // over/underflow.
+    let consumed_value = convert_value_ref_to_u128(&consumed_resource.value_ref);
+    let created_value = if is_addition {
+        consumed_value.checked_add(delta).expect("counter addition overflow")
+    } else {
+        consumed_value.checked_sub(delta).expect("counter subtraction underflow")
+    };
+    init_hello_world.set_value_ref(convert_hello_world_to_value_ref(created_value));
+
     // Update nullifier key commitment
     init_hello_world.set_nf_commitment(nf_key_cm.clone());
-    
+
     init_hello_world
 }
+
+/// Encodes a token quantity and owner tag into a 32-byte value reference.
+///
+/// The first sixteen bytes hold the quantity as a little-endian `u128`; the
+/// trailing sixteen bytes carry the owner/authorization tag, which
+/// [`TokenWitness::owner`](hello_world_witness::TokenWitness::owner) decodes;
+/// the tag is not placed in the logic instance's `AppData`.
+pub fn convert_token_to_value_ref(quantity: u128, owner: [u8; 16]) -> Vec<u8> {
+    let mut arr = [0u8; 32];
+    arr[..16].copy_from_slice(&quantity.to_le_bytes());
+    arr[16..].copy_from_slice(&owner);
+    arr.to_vec()
+}
+
+/// Generates a token resource of a given denomination, quantity and owner.
+///
+/// The denomination string is written into `label_ref`; the quantity and owner
+/// are packed into `value_ref`. `is_ephemeral` distinguishes a mint/burn pad
+/// resource from a persistent balance.
+///
+/// # Arguments
+///
+/// * `denomination` - The token identifier written into `label_ref`
+/// * `quantity` - The little-endian `u128` amount
+/// * `owner` - The sixteen-byte owner/authorization tag
+/// * `is_ephemeral` - Whether the resource is ephemeral (mint/burn pad)
+/// * `nk_commitment` - The nullifier key commitment for the resource
+///
+/// # Returns
+///
+/// A new token `Resource`
+pub fn generate_token_resource(
+    denomination: &[u8],
+    quantity: u128,
+    owner: [u8; 16],
+    is_ephemeral: bool,
+    nk_commitment: NullifierKeyCommitment,
+) -> Resource {
+    let logic_ref = TokenLogic::verifying_key_as_bytes();
+
+    let mut label_ref = [0u8; 32];
+    label_ref[..denomination.len()].copy_from_slice(denomination);
+
+    let value_ref = convert_token_to_value_ref(quantity, owner);
+
+    let mut rng = rand::thread_rng();
+    let nonce: [u8; 32] = rng.gen();
+
+    Resource::create(
+        logic_ref,
+        label_ref.to_vec(),
+        1,
+        value_ref,
+        is_ephemeral,
+        nonce.to_vec(),
+        nk_commitment,
+    )
+}
@@ -0,0 +1,118 @@
+//! Intent and solver subsystem for multi-party swaps.
+//!
+//! This module sits on top of the `create_transaction` flow: each party
+//! expresses a "give X, want Y" [`Intent`], and [`solve_intents`] assembles the
+//! balanced [`Transaction`] by collecting compliance units and logic proofs
+//! from every party, merging them into a single [`Action`], and producing one
+//! delta proof. Partial fulfillment is checked by the `IntentLogic` circuit:
+//! the offered side must be released proportionally, and the remainder intent
+//! resource that the solver creates must carry whatever is left unfilled.
+
+use crate::util::{aggregate_rcv, generate_compliance_proof};
+
+use arm::action::Action;
+use arm::delta_proof::DeltaWitness;
+use arm::logic_proof::{LogicProver, LogicVerifier};
+use arm::merkle_path::MerklePath;
+use arm::nullifier_key::NullifierKey;
+use arm::resource::Resource;
+use arm::transaction::{Delta, Transaction};
+use hello_world_library::IntentLogic;
+
+/// A single party's swap intent together with the material the solver needs to
+/// consume it.
+///
+/// `offered`/`wanted` describe the desired exchange rate; `filled_out` and
+/// `spent_in` record how much the solver actually released and delivered. When
+/// the intent is only partially filled, `is_partial` is set and the solver is
+/// expected to create a remainder intent resource for the unfilled part.
+pub struct Intent {
+    pub intent_resource: Resource,
+    pub nf_key: NullifierKey,
+    pub created_resource: Resource,
+    pub offered: u128,
+    pub wanted: u128,
+    pub filled_out: u128,
+    pub spent_in: u128,
+    pub is_partial: bool,
+}
+
+impl Intent {
+    /// Builds the logic proofs for this intent: one for the consumed intent and
+    /// one for the created (filled or remainder) resource.
+    fn logic_proofs(&self) -> Vec<LogicVerifier> {
+        use arm::action_tree::MerkleTree;
+
+        let nullifier = self.intent_resource.nullifier(&self.nf_key).unwrap();
+        let commitment = self.created_resource.commitment();
+        let action_tree = MerkleTree::new(vec![nullifier, commitment]);
+
+        let consumed_path = action_tree.generate_path(&nullifier).unwrap();
+        let consumed = IntentLogic::new(
+            true,
+            self.intent_resource.clone(),
+            consumed_path,
+            self.nf_key.clone(),
+            self.offered,
+            self.wanted,
+            self.filled_out,
+            self.spent_in,
+            self.is_partial,
+        )
+        .prove();
+
+        let created_path = action_tree.generate_path(&commitment).unwrap();
+        let created = IntentLogic::new(
+            false,
+            self.created_resource.clone(),
+            created_path,
+            self.nf_key.clone(),
+            self.offered,
+            self.wanted,
+            self.filled_out,
+            self.spent_in,
+            self.is_partial,
+        )
+        .prove();
+
+        vec![consumed, created]
+    }
+}
+
+/// Solves a set of intents into a single balanced transaction.
+///
+/// Each intent contributes a compliance unit and a pair of logic proofs; all of
+/// them are merged into one [`Action`] and the per-party RCV witnesses are
+/// aggregated into a single [`DeltaWitness`]. The resulting transaction's delta
+/// must balance to zero across every (possibly partial) fulfillment.
+///
+/// # Panics
+///
+/// Panics if any proof generation step fails.
+pub fn solve_intents(intents: Vec<Intent>) -> Transaction {
+    let mut compliance_units = Vec::new();
+    let mut logic_proofs = Vec::new();
+    let mut rcv_bytes = Vec::new();
+
+    for intent in &intents {
+        let (compliance_unit, rcv) = generate_compliance_proof(
+            intent.intent_resource.clone(),
+            intent.nf_key.clone(),
+            MerklePath::default(),
+            intent.created_resource.clone(),
+        );
+        compliance_units.push(compliance_unit);
+        logic_proofs.extend(intent.logic_proofs());
+        rcv_bytes.push(rcv);
+    }
+
+    // Merge every party's proofs into a single action.
+    let action = Action::new(compliance_units, logic_proofs);
+
+    // Aggregate the individual RCV witnesses so the combined delta balances.
+    let delta_witness = DeltaWitness::from_bytes(&aggregate_rcv(&rcv_bytes));
+    let mut transaction = Transaction::create(vec![action], Delta::Witness(delta_witness));
+    transaction.generate_delta_proof();
+
+    transaction
+}
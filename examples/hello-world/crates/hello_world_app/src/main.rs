@@ -5,16 +5,31 @@
 //! and one persistentresource, generates logic and compliance
 //! proofs, and creates a verifiable transaction.
 
+use crate::commitment_tree::CommitmentTree;
+use crate::incremental_tree::{scoped_tag, IncrementalMerkleTree};
 use crate::init::{generate_ephemeral_resource, generate_persistent_resource};
 use crate::util::{generate_compliance_proof, generate_logic_proofs};
 
+/// External-nullifier scope for this application, used to demonstrate scoping
+/// the incremental commitment log so the same resource yields a distinct leaf
+/// per application context.
+const APPLICATION_SCOPE: &[u8] = b"hello-world-counter";
+
 use arm::action::Action;
 use arm::delta_proof::DeltaWitness;
-use arm::merkle_path::MerklePath;
 use arm::nullifier_key::NullifierKey;
 use arm::transaction::{Delta, Transaction};
 
+mod commitment_tree;
+mod compose;
+mod demos;
+mod evm_verifier;
+mod incremental_tree;
 mod init;
+mod intent;
+mod note_encryption;
+mod randomness;
+mod swap;
 mod util;
 
 /// Creates and verifies a complete hello world transaction.
@@ -41,27 +56,68 @@ fn create_transaction() -> Transaction {
     // Step 2: Create persistent hello world resource
     println!("\nCreating persistent hello world resource...");
     let (_, persistent_nf_key_commitment) = NullifierKey::random_pair();
+    // Evolve the counter from 0 by adding a delta of 1 (the classic 0 -> 1
+    // hello-world transition, now expressed as an arithmetic step).
     let created_resource = generate_persistent_resource(
-        &consumed_resource, 
-        &ephemeral_nf_key, 
-        &persistent_nf_key_commitment
+        &consumed_resource,
+        &ephemeral_nf_key,
+        &persistent_nf_key_commitment,
+        1u128,
+        true,
+    );
+
+    // Step 3: Insert the consumed resource's commitment into the commitment
+    // tree and derive its real authentication path and anchor, so the
+    // compliance proof is checked against a published root rather than the
+    // empty `MerklePath::default()`.
+    println!("\nInserting commitment and deriving anchor...");
+    let mut commitment_tree = CommitmentTree::new();
+    let position = commitment_tree.insert(consumed_resource.commitment());
+    let merkle_path = commitment_tree.witness(position);
+    let anchor = commitment_tree.root();
+    println!("Commitment tree anchor: {:?}", anchor);
+
+    // Maintain an application-scoped, append-only commitment log so this
+    // context tracks its own spends independently of any other application. The
+    // leaf is scoped to APPLICATION_SCOPE to demonstrate external-nullifier
+    // scoping; the verifying transaction below stays unscoped so its logic
+    // leaves match the commitment/nullifier the compliance proof attests to.
+    let mut scoped_log = IncrementalMerkleTree::default_depth();
+    let leaf = scoped_tag(consumed_resource.commitment().as_bytes(), APPLICATION_SCOPE);
+    let log_position = scoped_log.insert(leaf);
+    println!("Application-scoped log root: {:?}", scoped_log.root());
+
+    // Generate the commitment's authentication path from the incremental tree's
+    // cached nodes (no full rebuild) and confirm it reproduces the log root.
+    let log_path = scoped_log.witness(log_position);
+    assert_eq!(
+        IncrementalMerkleTree::root_from_path(leaf, &log_path),
+        scoped_log.root(),
+        "incremental log path does not reproduce the root"
     );
 
-    // Step 3: Generate compliance proof
+    // Step 3b: Generate compliance proof against the real path.
     println!("\nCreating compliance proof...");
     let (compliance_unit, rcv) = generate_compliance_proof(
         consumed_resource.clone(),
         ephemeral_nf_key.clone(),
-        MerklePath::default(),
+        merkle_path,
         created_resource.clone(),
     );
 
-    // Step 4: Generate logic proofs
+    // Step 4: Generate logic proofs, sealing the created resource to the
+    // receiver's transmission key so the ciphertext is bound into its proof.
     println!("\nCreating logic proofs...");
+    let recipient_sk = x25519_dalek::StaticSecret::random_from_rng(rand::rngs::OsRng);
+    let recipient_pk = x25519_dalek::PublicKey::from(&recipient_sk).to_bytes();
     let logic_verifier_inputs = generate_logic_proofs(
         consumed_resource,
         ephemeral_nf_key,
         created_resource,
+        1u128,
+        true,
+        Vec::new(),
+        Some(recipient_pk),
     );
 
     // Step 5: Create transaction action
@@ -77,13 +133,14 @@ fn create_transaction() -> Transaction {
     println!("\nGenerating delta proof...");
     transaction.generate_delta_proof();
 
-    // Verify the transaction
+    // Verify the transaction. Assert rather than log so the headline demo fails
+    // loudly if it ever stops verifying.
     println!("\nVerifying transaction...");
-    if transaction.clone().verify() {
-        println!("Transaction verified successfully");
-    } else {
-        println!("Transaction verification failed");
-    }
+    assert!(transaction.clone().verify(), "transaction verification failed");
+    println!("Transaction verified successfully");
+
+    // Emitting an EVM-verifiable artifact (see `evm_verifier`) is opt-in: it
+    // requires the Groth16 prover, so it is intentionally not run here.
 
     transaction
 }
@@ -96,12 +153,35 @@ fn main() {
     
     // Create and verify the transaction
     let transaction = create_transaction();
-    
+
     // Display transaction details
     println!("\nTransaction Details:");
     println!("{:?}", transaction);
-    
+
+    // Exercise the token, auth, swap and composition subsystems.
+    run_worked_examples(&transaction);
+
     // let _ = submit_transaction(transaction);
-    
+
     println!("\nTransaction completed successfully!");
 }
+
+/// Runs the remaining subsystems' worked examples so each has a live call-site
+/// alongside the headline counter flow.
+///
+/// They require the RISC0 prover — and the EVM artifact additionally needs the
+/// Groth16 prover — so they are gated behind `RUN_EXTENDED_EXAMPLES` and left
+/// off in the default demo. Flip it to `true` on a host with the proving stack
+/// to drive them end to end.
+fn run_worked_examples(counter_tx: &Transaction) {
+    const RUN_EXTENDED_EXAMPLES: bool = false;
+    if !RUN_EXTENDED_EXAMPLES {
+        return;
+    }
+
+    let _ = demos::demo_token_transfer();
+    let _ = demos::demo_auth_consumption();
+    let _ = demos::demo_swap();
+    let _ = demos::demo_compose();
+    demos::emit_evm_artifact(counter_tx);
+}
@@ -0,0 +1,75 @@
+//! In-band encryption of resource notes to a recipient public key.
+//!
+//! When a resource is created, the creator can attach an encrypted payload (for
+//! example the resource value, nonce and nullifier commitment) addressed to the
+//! intended receiver. Encryption follows the usual ECDH + AEAD construction: an
+//! ephemeral X25519 key agrees a shared secret with the recipient's public key,
+//! the secret is expanded with a KDF, and the plaintext is sealed with
+//! ChaCha20-Poly1305. The resulting [`EncryptedNote`] is carried in the
+//! resource's `AppData` and committed into the logic proof, so the ciphertext
+//! and ephemeral key are fixed alongside the resource's tag and cannot be
+//! swapped after the fact. The proof does not check that the ciphertext
+//! decrypts to the committed resource — that correspondence cannot be verified
+//! in-circuit without the recipient's key and is instead checked receiver-side
+//! on decryption.
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
+use x25519_dalek::{EphemeralSecret, PublicKey, StaticSecret};
+
+/// A note sealed to a recipient's transmission key.
+#[derive(Clone, Default)]
+pub struct EncryptedNote {
+    /// The authenticated ciphertext.
+    pub ciphertext: Vec<u8>,
+    /// The ephemeral X25519 public key used for the key agreement.
+    pub ephemeral_pubkey: [u8; 32],
+}
+
+/// Expands a raw ECDH shared secret into a symmetric AEAD key.
+fn kdf(shared_secret: &[u8], ephemeral_pubkey: &[u8; 32]) -> Key {
+    let mut hasher = Sha256::new();
+    hasher.update(b"ARM.note-encryption");
+    hasher.update(shared_secret);
+    hasher.update(ephemeral_pubkey);
+    *Key::from_slice(&hasher.finalize())
+}
+
+/// The AEAD nonce is fixed: each note uses a fresh ephemeral key, so the
+/// (key, nonce) pair is never reused.
+fn aead_nonce() -> Nonce {
+    *Nonce::from_slice(&[0u8; 12])
+}
+
+/// Encrypts `plaintext` to the recipient's X25519 public key.
+pub fn encrypt_note(recipient_pk: &[u8; 32], plaintext: &[u8]) -> EncryptedNote {
+    let ephemeral_secret = EphemeralSecret::random_from_rng(OsRng);
+    let ephemeral_pubkey = PublicKey::from(&ephemeral_secret).to_bytes();
+
+    let shared_secret = ephemeral_secret.diffie_hellman(&PublicKey::from(*recipient_pk));
+    let key = kdf(shared_secret.as_bytes(), &ephemeral_pubkey);
+
+    let cipher = ChaCha20Poly1305::new(&key);
+    let ciphertext = cipher
+        .encrypt(&aead_nonce(), plaintext)
+        .expect("note encryption failed");
+
+    EncryptedNote {
+        ciphertext,
+        ephemeral_pubkey,
+    }
+}
+
+/// Decrypts an [`EncryptedNote`] on the recipient side with their secret key.
+pub fn decrypt_note(recipient_sk: &[u8; 32], note: &EncryptedNote) -> Vec<u8> {
+    let secret = StaticSecret::from(*recipient_sk);
+    let shared_secret = secret.diffie_hellman(&PublicKey::from(note.ephemeral_pubkey));
+    let key = kdf(shared_secret.as_bytes(), &note.ephemeral_pubkey);
+
+    let cipher = ChaCha20Poly1305::new(&key);
+    cipher
+        .decrypt(&aead_nonce(), note.ciphertext.as_ref())
+        .expect("note decryption failed")
+}
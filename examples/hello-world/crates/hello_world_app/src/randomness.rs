@@ -0,0 +1,49 @@
+//! Deterministic, single-seed randomness derivation for resources.
+//!
+//! A resource's commitment randomness is parameterised by its 32-byte
+//! `rand_seed`. [`Resource::reset_randomness`] draws a *fresh* random seed each
+//! time, so a resource can only be reconstructed later if that sampled seed was
+//! stored out of band.
+//!
+//! [`DeriveRandomness::derive_randomness`] replaces that fresh sample with a
+//! domain-separated derivation from the resource's current seed and nonce:
+//!
+//! ```text
+//! rand_seed' = PRF(rand_seed, nonce)
+//! ```
+//!
+//! so the seed itself becomes reproducible from a single backed-up
+//! `(rand_seed, nonce)` pair rather than having to be sampled and stored
+//! separately. To the extent `arm` derives the rest of a resource's randomness
+//! from `rand_seed`, recovering the seed recovers the whole resource; the exact
+//! derivation is owned by `arm`'s commitment scheme and is not reproduced here.
+//! Ephemeral resources keep the randomly sampled seed from `Resource::create`;
+//! persistent resources re-derive deterministically so they can be recovered
+//! from a seed backup.
+
+use arm::resource::Resource;
+use sha2::{Digest, Sha256};
+
+/// A domain-separated PRF over `(rand_seed, nonce)` returning a 32-byte seed.
+fn derive_seed(rand_seed: &[u8], nonce: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"ARM.rseed");
+    hasher.update(rand_seed);
+    hasher.update(nonce);
+    hasher.finalize().into()
+}
+
+/// Reconstructible randomness derivation for [`Resource`].
+pub trait DeriveRandomness {
+    /// Derives the resource's `rand_seed` deterministically from its current
+    /// seed and `nonce`, replacing [`Resource::reset_randomness`]'s fresh sample
+    /// so the seed — and whatever randomness `arm` derives from it — is
+    /// recoverable from a `(rand_seed, nonce)` backup.
+    fn derive_randomness(&mut self);
+}
+
+impl DeriveRandomness for Resource {
+    fn derive_randomness(&mut self) {
+        self.rand_seed = derive_seed(&self.rand_seed, &self.nonce).to_vec();
+    }
+}
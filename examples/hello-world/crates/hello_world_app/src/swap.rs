@@ -0,0 +1,91 @@
+//! Two-party intent swap example with partial fulfillment.
+//!
+//! This worked example sits alongside [`init`](crate::init) and
+//! [`util`](crate::util) and shows how to express a swap as an intent
+//! resource whose `value_ref` encodes [`SwapTerms`], and settle it with the
+//! solver from
+//! [`intent`](crate::intent). Each party publishes an intent encoding what it
+//! gives and the minimum it wants; a solver consumes both intents together with
+//! the matching output resources, either satisfying each want fully or creating
+//! a residual intent for the unfilled part.
+
+use crate::intent::{solve_intents, Intent};
+
+use arm::nullifier_key::NullifierKey;
+use arm::resource::Resource;
+use arm::transaction::Transaction;
+
+/// Decoded view of an intent's `value_ref`.
+///
+/// The 32-byte `value_ref` is laid out as four little-endian `u64` fields:
+/// `give_kind | give_quantity | want_kind | want_quantity`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapTerms {
+    pub give_kind: u64,
+    pub give_quantity: u64,
+    pub want_kind: u64,
+    pub want_quantity: u64,
+}
+
+impl SwapTerms {
+    /// Encodes the swap terms into a 32-byte `value_ref`.
+    pub fn to_value_ref(self) -> Vec<u8> {
+        let mut arr = [0u8; 32];
+        arr[0..8].copy_from_slice(&self.give_kind.to_le_bytes());
+        arr[8..16].copy_from_slice(&self.give_quantity.to_le_bytes());
+        arr[16..24].copy_from_slice(&self.want_kind.to_le_bytes());
+        arr[24..32].copy_from_slice(&self.want_quantity.to_le_bytes());
+        arr.to_vec()
+    }
+
+    /// Decodes swap terms from a 32-byte `value_ref`.
+    pub fn from_value_ref(value_ref: &[u8]) -> Self {
+        let field = |r: core::ops::Range<usize>| {
+            u64::from_le_bytes(value_ref[r].try_into().unwrap())
+        };
+        Self {
+            give_kind: field(0..8),
+            give_quantity: field(8..16),
+            want_kind: field(16..24),
+            want_quantity: field(24..32),
+        }
+    }
+}
+
+/// A party's offer: its intent resource together with the key needed to consume
+/// it and the resource the solver creates in return (the filled output, or a
+/// residual intent when only partially filled).
+pub struct Offer {
+    pub intent_resource: Resource,
+    pub nf_key: NullifierKey,
+    pub created_resource: Resource,
+    pub filled: u64,
+    pub spent: u64,
+    pub is_partial: bool,
+}
+
+impl Offer {
+    fn into_intent(self) -> Intent {
+        let terms = SwapTerms::from_value_ref(&self.intent_resource.value_ref);
+        Intent {
+            intent_resource: self.intent_resource,
+            nf_key: self.nf_key,
+            created_resource: self.created_resource,
+            offered: terms.give_quantity as u128,
+            wanted: terms.want_quantity as u128,
+            filled_out: self.filled as u128,
+            spent_in: self.spent as u128,
+            is_partial: self.is_partial,
+        }
+    }
+}
+
+/// Settles a set of offers into a single balanced swap transaction.
+///
+/// This is the solver entry point for the worked example: it converts each
+/// [`Offer`] into an [`Intent`] and hands the batch to [`solve_intents`], which
+/// proves each leaf and produces one delta proof balancing all fulfillments.
+pub fn settle_swap(offers: Vec<Offer>) -> Transaction {
+    let intents = offers.into_iter().map(Offer::into_intent).collect();
+    solve_intents(intents)
+}
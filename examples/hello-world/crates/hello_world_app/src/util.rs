@@ -10,7 +10,10 @@ use arm::logic_proof::{LogicProver, LogicVerifier};
 use arm::merkle_path::MerklePath;
 use arm::nullifier_key::NullifierKey;
 use arm::resource::Resource;
-use hello_world_library::HelloWorldLogic;
+use ed25519_dalek::{Signer, SigningKey};
+use hello_world_library::{AuthLogic, HelloWorldLogic, TokenLogic};
+use hello_world_witness::TokenBalanceEntry;
+use risc0_zkvm::sha::Digest;
 
 /// Generates a compliance proof for resource consumption and creation.
 /// 
@@ -87,38 +90,327 @@ pub fn generate_logic_proofs(
     consumed_resource: Resource,
     nullifier_key: NullifierKey,
     created_resource: Resource,
+    delta: u128,
+    is_addition: bool,
+    external_nullifier: Vec<u8>,
+    recipient_pk: Option<[u8; 32]>,
 ) -> Vec<LogicVerifier> {
     // Generate nullifier for consumed resource and commitment for created resource
     let consumed_resource_nullifier = consumed_resource.nullifier(&nullifier_key).unwrap();
     let created_resource_commitment = created_resource.commitment();
 
-    // Create action tree with both nullifier and commitment
-    let action_tree = MerkleTree::new(vec![consumed_resource_nullifier, created_resource_commitment]);
+    // Read the counter values out of each resource so the circuit can enforce
+    // the `created == consumed +/- delta` transition.
+    let consumed_value =
+        u128::from_le_bytes(consumed_resource.value_ref[0..16].try_into().unwrap());
+    let created_value =
+        u128::from_le_bytes(created_resource.value_ref[0..16].try_into().unwrap());
 
-    // Generate merkle path for consumed resource
-    let consumed_resource_path = action_tree.generate_path(&consumed_resource_nullifier).unwrap();
+    // When the proofs are scoped to an external nullifier, the circuit roots
+    // against the scoped tag, so the action tree must carry the matching scoped
+    // leaves (computed with the same hashing as the witness).
+    let (consumed_leaf, created_leaf) = if external_nullifier.is_empty() {
+        (consumed_resource_nullifier, created_resource_commitment)
+    } else {
+        let consumed = crate::incremental_tree::scoped_tag(
+            consumed_resource_nullifier.as_bytes(),
+            &external_nullifier,
+        );
+        let created = crate::incremental_tree::scoped_tag(
+            created_resource_commitment.as_bytes(),
+            &external_nullifier,
+        );
+        (Digest::from_bytes(consumed), Digest::from_bytes(created))
+    };
 
-    // Create and prove logic for consumed resource (is_consumed = true)
-    let consumed_resource_logic = HelloWorldLogic::new(
+    // Create action tree with both scoped leaves
+    let action_tree = MerkleTree::new(vec![consumed_leaf, created_leaf]);
+
+    // Generate merkle paths for both leaves up front: each proof binds its
+    // counterpart's leaf, so both paths are needed before either proof is built.
+    let consumed_resource_path = action_tree.generate_path(&consumed_leaf).unwrap();
+    let created_resource_path = action_tree.generate_path(&created_leaf).unwrap();
+
+    // Create and prove logic for consumed resource (is_consumed = true),
+    // binding the created resource as its counterpart.
+    let consumed_resource_logic = HelloWorldLogic::new_counter(
         true,
         consumed_resource.clone(),
         consumed_resource_path.clone(),
-        nullifier_key.clone()
+        nullifier_key.clone(),
+        consumed_value,
+        created_value,
+        delta,
+        is_addition,
+    )
+    .with_counter_counterpart(
+        created_resource.clone(),
+        created_resource_path.clone(),
+        nullifier_key.clone(),
+    )
+    .with_external_nullifier(external_nullifier.clone());
+    let consumed_logic_proof = consumed_resource_logic.prove();
+
+    // When a recipient is given, seal the created resource to their
+    // transmission key and bind the ciphertext into its proof.
+    let encrypted_note = recipient_pk.map(|pk| encrypt_resource(&pk, &created_resource));
+
+    // Create and prove logic for created resource (is_consumed = false),
+    // binding the consumed resource as its counterpart.
+    let mut created_resource_logic = HelloWorldLogic::new_counter(
+        false,
+        created_resource.clone(),
+        created_resource_path,
+        nullifier_key.clone(),
+        consumed_value,
+        created_value,
+        delta,
+        is_addition,
+    )
+    .with_counter_counterpart(
+        consumed_resource.clone(),
+        consumed_resource_path,
+        nullifier_key,
+    )
+    .with_external_nullifier(external_nullifier);
+    if let Some((ciphertext, ephemeral_pubkey)) = encrypted_note {
+        created_resource_logic =
+            created_resource_logic.with_encrypted_note(ciphertext, ephemeral_pubkey.to_vec());
+    }
+    let created_logic_proof = created_resource_logic.prove();
+
+    // Return both proofs in order: consumed first, then created
+    vec![consumed_logic_proof, created_logic_proof]
+}
+
+/// Generates token logic proofs for a mint/transfer/burn action.
+///
+/// Unlike [`generate_logic_proofs`], which proves a single consumed/created
+/// pair, this emits one [`TokenLogic`] proof per resource in the action: every
+/// consumed token resource is proven with `is_consumed = true` and every
+/// created one with `is_consumed = false`. Each proof carries the full vectors
+/// of consumed and created quantities so the circuit can enforce value
+/// conservation for the denomination.
+///
+/// # Arguments
+///
+/// * `consumed_resources` - The token resources being consumed, each with its nullifier key
+/// * `created_resources` - The token resources being created
+///
+/// # Returns
+///
+/// A vector of `LogicVerifier` proofs, consumed resources first then created.
+///
+/// # Panics
+///
+/// This function may panic if nullifier, merkle path, or proof generation fails.
+pub fn generate_token_logic_proofs(
+    consumed_resources: Vec<(Resource, NullifierKey)>,
+    created_resources: Vec<Resource>,
+) -> Vec<LogicVerifier> {
+    // Build the action tree over every consumed nullifier and created commitment.
+    let mut leaves = Vec::new();
+    let consumed_nullifiers: Vec<_> = consumed_resources
+        .iter()
+        .map(|(r, k)| r.nullifier(k).unwrap())
+        .collect();
+    let created_commitments: Vec<_> = created_resources.iter().map(|r| r.commitment()).collect();
+    leaves.extend(consumed_nullifiers.iter().cloned());
+    leaves.extend(created_commitments.iter().cloned());
+    let action_tree = MerkleTree::new(leaves);
+
+    // Collect each resource's balance contribution, binding its (denomination,
+    // quantity) to the real action-tree leaf it occupies and an authentication
+    // path rooting that leaf to this tree. The circuit rejects any balance entry
+    // whose path does not root here, so a quantity can only be counted if it is
+    // backed by a committed nullifier/commitment.
+    let consumed_balance: Vec<TokenBalanceEntry> = consumed_resources
+        .iter()
+        .zip(&consumed_nullifiers)
+        .map(|((r, _), nullifier)| TokenBalanceEntry {
+            denom: r.label_ref.clone(),
+            quantity: u128::from_le_bytes(r.value_ref[0..16].try_into().unwrap()),
+            tag: *nullifier,
+            path: action_tree.generate_path(nullifier).unwrap(),
+        })
+        .collect();
+    let created_balance: Vec<TokenBalanceEntry> = created_resources
+        .iter()
+        .zip(&created_commitments)
+        .map(|(r, commitment)| TokenBalanceEntry {
+            denom: r.label_ref.clone(),
+            quantity: u128::from_le_bytes(r.value_ref[0..16].try_into().unwrap()),
+            tag: *commitment,
+            path: action_tree.generate_path(commitment).unwrap(),
+        })
+        .collect();
+
+    let mut proofs = Vec::new();
+
+    // One proof per consumed token resource.
+    for ((resource, nf_key), nullifier) in consumed_resources.iter().zip(&consumed_nullifiers) {
+        let path = action_tree.generate_path(nullifier).unwrap();
+        let logic = TokenLogic::new(
+            true,
+            resource.clone(),
+            path,
+            nf_key.clone(),
+            consumed_balance.clone(),
+            created_balance.clone(),
+        );
+        proofs.push(logic.prove());
+    }
+
+    // One proof per created token resource.
+    for (resource, commitment) in created_resources.iter().zip(&created_commitments) {
+        let path = action_tree.generate_path(commitment).unwrap();
+        let logic = TokenLogic::new(
+            false,
+            resource.clone(),
+            path,
+            NullifierKey::default(),
+            consumed_balance.clone(),
+            created_balance.clone(),
+        );
+        proofs.push(logic.prove());
+    }
+
+    proofs
+}
+
+/// Generates logic proofs for a signature-authorized consumption.
+///
+/// Parallel to [`generate_logic_proofs`], but the consumed resource is gated by
+/// an Ed25519 signature: the nullifier (tag) of the consumed resource is signed
+/// with `signing_key` and the signature is threaded into the [`AuthLogic`]
+/// witness, while the created resource is proven without an authorization
+/// requirement. Both leaves are wired into the same action tree.
+///
+/// # Arguments
+///
+/// * `consumed_resource` - The signature-gated resource being consumed
+/// * `nullifier_key` - The nullifier key used to consume the resource
+/// * `created_resource` - The resource being created
+/// * `signing_key` - The Ed25519 signing key whose public half is committed in `value_ref`
+///
+/// # Returns
+///
+/// A vector containing the consumed proof followed by the created proof.
+///
+/// # Panics
+///
+/// This function may panic if nullifier, merkle path, or proof generation fails.
+pub fn generate_auth_logic_proofs(
+    consumed_resource: Resource,
+    nullifier_key: NullifierKey,
+    created_resource: Resource,
+    signing_key: &SigningKey,
+) -> Vec<LogicVerifier> {
+    let consumed_resource_nullifier = consumed_resource.nullifier(&nullifier_key).unwrap();
+    let created_resource_commitment = created_resource.commitment();
+
+    let action_tree =
+        MerkleTree::new(vec![consumed_resource_nullifier, created_resource_commitment]);
+
+    // Sign the consumed resource's nullifier so the circuit can authorize the
+    // spend. For a consumed resource the tag is exactly its nullifier.
+    let signature = sign_consumption(&consumed_resource_nullifier, signing_key);
+    let verifying_key = signing_key.verifying_key().to_bytes();
+
+    let consumed_resource_path = action_tree
+        .generate_path(&consumed_resource_nullifier)
+        .unwrap();
+    let consumed_resource_logic = AuthLogic::new(
+        true,
+        consumed_resource,
+        consumed_resource_path,
+        nullifier_key.clone(),
+        signature.to_vec(),
+        verifying_key,
     );
     let consumed_logic_proof = consumed_resource_logic.prove();
 
-    // Generate merkle path for created resource
-    let created_resource_path = action_tree.generate_path(&created_resource_commitment).unwrap();
-    
-    // Create and prove logic for created resource (is_consumed = false)
-    let created_resource_logic = HelloWorldLogic::new(
+    let created_resource_path = action_tree
+        .generate_path(&created_resource_commitment)
+        .unwrap();
+    let created_resource_logic = AuthLogic::new(
         false,
         created_resource,
         created_resource_path,
         nullifier_key,
+        Vec::new(),
+        verifying_key,
     );
     let created_logic_proof = created_resource_logic.prove();
 
-    // Return both proofs in order: consumed first, then created
     vec![consumed_logic_proof, created_logic_proof]
 }
+
+/// Encrypts a created resource to its intended owner's transmission key.
+///
+/// Modeled on Orchard's in-band secret distribution: the full resource opening
+/// (nonce, value_ref, nf-key commitment and randomness) is serialized, sealed
+/// with the shared-secret AEAD from [`crate::note_encryption`], and returned
+/// together with the ephemeral public key. The guest logic commits to the
+/// ciphertext and ephemeral key in its journal so the ciphertext is bound to
+/// the proof.
+///
+/// # Returns
+///
+/// A tuple of `(ciphertext, ephemeral_pubkey)` to transmit to the receiver.
+pub fn encrypt_resource(recipient_pk: &[u8; 32], resource: &Resource) -> (Vec<u8>, [u8; 32]) {
+    let plaintext = bincode::serialize(resource).expect("resource serialization failed");
+    let note = crate::note_encryption::encrypt_note(recipient_pk, &plaintext);
+    (note.ciphertext, note.ephemeral_pubkey)
+}
+
+/// Reconstructs a [`Resource`] from a ciphertext and ephemeral key using the
+/// recipient's secret transmission key.
+///
+/// This is the inverse of [`encrypt_resource`]: it recovers the shared secret
+/// via Diffie-Hellman, decrypts the sealed opening, and deserializes the
+/// resource so the receiver can detect and later consume it.
+pub fn decrypt_resource(sk: &[u8; 32], ciphertext: &[u8], epk: &[u8; 32]) -> Resource {
+    let note = crate::note_encryption::EncryptedNote {
+        ciphertext: ciphertext.to_vec(),
+        ephemeral_pubkey: *epk,
+    };
+    let plaintext = crate::note_encryption::decrypt_note(sk, &note);
+    bincode::deserialize(&plaintext).expect("resource deserialization failed")
+}
+
+/// Aggregates several delta-witness RCVs into one combined witness.
+///
+/// Each RCV is the blinding scalar of one action's delta witness. The delta of
+/// a merged transaction is the sum of the per-action deltas, so the combined
+/// witness is the *sum of the scalars* (reduced modulo the delta curve order),
+/// not their concatenation: `DeltaWitness::from_bytes` expects a single scalar,
+/// and only the sum reconstructs the randomness that cancels the intermediate
+/// resources.
+///
+/// # Panics
+///
+/// Panics if any RCV is not a canonical 32-byte scalar.
+pub fn aggregate_rcv(rcvs: &[Vec<u8>]) -> Vec<u8> {
+    use k256::elliptic_curve::{Field, PrimeField};
+
+    let mut acc = k256::Scalar::ZERO;
+    for rcv in rcvs {
+        let mut repr = k256::FieldBytes::default();
+        repr.copy_from_slice(rcv);
+        let scalar = Option::<k256::Scalar>::from(k256::Scalar::from_repr(repr))
+            .expect("rcv is not a canonical scalar");
+        acc += scalar;
+    }
+    acc.to_bytes().to_vec()
+}
+
+/// Signs a resource nullifier to authorize its consumption.
+///
+/// The owner of a signature-gated resource produces this signature over the
+/// nullifier of the resource being spent; it is threaded into the [`AuthLogic`]
+/// witness (see [`generate_auth_logic_proofs`]) and checked in-circuit against
+/// the verifying key committed in the resource's `value_ref`.
+pub fn sign_consumption(nullifier: &Digest, signing_key: &SigningKey) -> [u8; 64] {
+    signing_key.sign(nullifier.as_bytes()).to_bytes()
+}
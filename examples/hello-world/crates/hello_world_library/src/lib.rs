@@ -3,17 +3,31 @@ use arm::{
     merkle_path::MerklePath, nullifier_key::NullifierKey,
     resource::Resource,
 };
-use hello_world_witness::HelloWorldWitness;
+use hello_world_witness::{AuthWitness, HelloWorldWitness, IntentWitness, TokenBalanceEntry, TokenWitness};
 use hex::FromHex;
 use lazy_static::lazy_static;
-use risc0_zkvm::Digest;
+use risc0_zkvm::{compute_image_id, Digest};
 use serde::{Deserialize, Serialize};
 
 pub const HELLO_WORLD_ELF: &[u8] = include_bytes!("../elf/hello_world_guest.bin");
+pub const TOKEN_ELF: &[u8] = include_bytes!("../elf/token_guest.bin");
+pub const AUTH_ELF: &[u8] = include_bytes!("../elf/auth_guest.bin");
+pub const INTENT_ELF: &[u8] = include_bytes!("../elf/intent_guest.bin");
 lazy_static! {
     pub static ref HELLO_WORLD_ID: Digest =
         Digest::from_hex("d1dc300a67141213bd29c2cacc550aa37fa3cd062e59a977facc8826e01cfcce")
             .unwrap();
+    // The hello-world guest ID is pinned to the value printed by
+    // `print_counter_elf_id`. The token/auth/intent guests were added later and
+    // their committed ELFs are the source of truth, so their image IDs are
+    // computed from the ELF bytes rather than pinned by hand; this guarantees
+    // the verifying key always matches the proving key actually used.
+    pub static ref TOKEN_ID: Digest =
+        compute_image_id(TOKEN_ELF).expect("invalid token guest ELF");
+    pub static ref AUTH_ID: Digest =
+        compute_image_id(AUTH_ELF).expect("invalid auth guest ELF");
+    pub static ref INTENT_ID: Digest =
+        compute_image_id(INTENT_ELF).expect("invalid intent guest ELF");
 }
 
 #[derive(Clone, Default, Deserialize, Serialize)]
@@ -39,6 +53,62 @@ impl HelloWorldLogic {
     }
 }
 
+impl HelloWorldLogic {
+    /// Builds a hello-world logic that enforces a counter state transition
+    /// (`created == consumed +/- delta`) in addition to the label check.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_counter(
+        is_consumed: bool,
+        hello_world: Resource,
+        hello_world_existence_path: MerklePath,
+        nf_key: NullifierKey,
+        consumed_value: u128,
+        created_value: u128,
+        delta: u128,
+        is_addition: bool,
+    ) -> Self {
+        Self {
+            witness: HelloWorldWitness::new(
+                is_consumed,
+                hello_world,
+                hello_world_existence_path,
+                nf_key,
+            )
+            .with_counter_transition(consumed_value, created_value, delta, is_addition),
+        }
+    }
+
+    /// Binds the counterpart side of a counter transition (the created resource
+    /// when this proof is for the consumed side, and vice versa) so the counter
+    /// relation is enforced across both action-tree leaves.
+    pub fn with_counter_counterpart(
+        mut self,
+        counterpart: Resource,
+        counterpart_path: MerklePath,
+        counterpart_nf_key: NullifierKey,
+    ) -> Self {
+        self.witness =
+            self.witness
+                .with_counter_counterpart(counterpart, counterpart_path, counterpart_nf_key);
+        self
+    }
+
+    /// Scopes the logic to an external nullifier (domain/epoch/application) so
+    /// the resource's tag is distinct per context and cannot be double-spent
+    /// across them.
+    pub fn with_external_nullifier(mut self, external_nullifier: Vec<u8>) -> Self {
+        self.witness = self.witness.with_external_nullifier(external_nullifier);
+        self
+    }
+
+    /// Attaches an encrypted note to the resource so its ciphertext and
+    /// ephemeral key are carried in `AppData` and bound into the logic proof.
+    pub fn with_encrypted_note(mut self, ciphertext: Vec<u8>, ephemeral_pubkey: Vec<u8>) -> Self {
+        self.witness = self.witness.with_encrypted_note(ciphertext, ephemeral_pubkey);
+        self
+    }
+}
+
 impl LogicProver for HelloWorldLogic {
     type Witness = HelloWorldWitness;
     fn proving_key() -> &'static [u8] {
@@ -53,3 +123,153 @@ impl LogicProver for HelloWorldLogic {
         &self.witness
     }
 }
+
+/// Quantity-conserving token resource logic.
+///
+/// Mirrors [`HelloWorldLogic`] but proves the [`TokenWitness`] circuit, which
+/// reads `label_ref` as a token denomination and `value_ref` as a little-endian
+/// `u128` quantity and enforces that consumed and created quantities balance
+/// within the action.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct TokenLogic {
+    witness: TokenWitness,
+}
+
+impl TokenLogic {
+    pub fn new(
+        is_consumed: bool,
+        token: Resource,
+        token_existence_path: MerklePath,
+        nf_key: NullifierKey,
+        consumed_balance: Vec<TokenBalanceEntry>,
+        created_balance: Vec<TokenBalanceEntry>,
+    ) -> Self {
+        Self {
+            witness: TokenWitness::new(
+                is_consumed,
+                token,
+                token_existence_path,
+                nf_key,
+                consumed_balance,
+                created_balance,
+            ),
+        }
+    }
+}
+
+impl LogicProver for TokenLogic {
+    type Witness = TokenWitness;
+    fn proving_key() -> &'static [u8] {
+        TOKEN_ELF
+    }
+
+    fn verifying_key() -> Digest {
+        *TOKEN_ID
+    }
+
+    fn witness(&self) -> &Self::Witness {
+        &self.witness
+    }
+}
+
+/// Signature-authorized consumption resource logic.
+///
+/// Proves the [`AuthWitness`] circuit, which verifies an Ed25519 signature over
+/// the resource tag against the verifying key committed in the resource
+/// `value_ref`. Only the holder of the matching signing key can consume the
+/// resource.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct AuthLogic {
+    witness: AuthWitness,
+}
+
+impl AuthLogic {
+    pub fn new(
+        is_consumed: bool,
+        auth_resource: Resource,
+        auth_existence_path: MerklePath,
+        nf_key: NullifierKey,
+        signature: Vec<u8>,
+        verifying_key: [u8; 32],
+    ) -> Self {
+        Self {
+            witness: AuthWitness::new(
+                is_consumed,
+                auth_resource,
+                auth_existence_path,
+                nf_key,
+                signature,
+                verifying_key,
+            ),
+        }
+    }
+}
+
+impl LogicProver for AuthLogic {
+    type Witness = AuthWitness;
+    fn proving_key() -> &'static [u8] {
+        AUTH_ELF
+    }
+
+    fn verifying_key() -> Digest {
+        *AUTH_ID
+    }
+
+    fn witness(&self) -> &Self::Witness {
+        &self.witness
+    }
+}
+
+/// Intent resource logic for solver-assembled swaps.
+///
+/// Proves the [`IntentWitness`] circuit, which accepts the consumption of an
+/// intent only when it is fully satisfied, or partially filled with a
+/// proportional release of the offered side and a matching remainder intent.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct IntentLogic {
+    witness: IntentWitness,
+}
+
+impl IntentLogic {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        is_consumed: bool,
+        intent: Resource,
+        intent_existence_path: MerklePath,
+        nf_key: NullifierKey,
+        offered: u128,
+        wanted: u128,
+        filled_out: u128,
+        spent_in: u128,
+        is_partial: bool,
+    ) -> Self {
+        Self {
+            witness: IntentWitness::new(
+                is_consumed,
+                intent,
+                intent_existence_path,
+                nf_key,
+                offered,
+                wanted,
+                filled_out,
+                spent_in,
+                is_partial,
+            ),
+        }
+    }
+}
+
+impl LogicProver for IntentLogic {
+    type Witness = IntentWitness;
+    fn proving_key() -> &'static [u8] {
+        INTENT_ELF
+    }
+
+    fn verifying_key() -> Digest {
+        *INTENT_ID
+    }
+
+    fn witness(&self) -> &Self::Witness {
+        &self.witness
+    }
+}
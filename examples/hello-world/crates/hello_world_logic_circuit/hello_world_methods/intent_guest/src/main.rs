@@ -0,0 +1,13 @@
+use risc0_zkvm::guest::env;
+use hello_world_witness::{IntentWitness, LogicCircuit};
+
+fn main() {
+    // read the input
+    let witness: IntentWitness = env::read();
+
+    // process constraints
+    let instance = witness.constrain();
+
+    // write public output to the journal
+    env::commit(&instance);
+}
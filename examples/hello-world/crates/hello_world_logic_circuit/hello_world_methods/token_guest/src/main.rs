@@ -0,0 +1,13 @@
+use risc0_zkvm::guest::env;
+use hello_world_witness::{TokenWitness, LogicCircuit};
+
+fn main() {
+    // read the input
+    let witness: TokenWitness = env::read();
+
+    // process constraints
+    let instance = witness.constrain();
+
+    // write public output to the journal
+    env::commit(&instance);
+}
@@ -5,7 +5,10 @@ use arm::{
     nullifier_key::NullifierKey,
     resource::Resource,
 };
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use risc0_zkvm::sha::Digest;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
 
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct HelloWorldWitness {
@@ -13,6 +16,45 @@ pub struct HelloWorldWitness {
     pub hello_world: Resource,
     pub hello_world_existence_path: MerklePath,
     pub nf_key: NullifierKey,
+    /// Encrypted note addressed to the receiver; empty when no payload is attached.
+    #[serde(default)]
+    pub ciphertext: Vec<u8>,
+    /// Ephemeral X25519 public key accompanying `ciphertext`.
+    #[serde(default)]
+    pub ephemeral_pubkey: Vec<u8>,
+    /// External-nullifier scope (domain/epoch/application identifier) hashed
+    /// into the tag; empty for the unscoped default context.
+    #[serde(default)]
+    pub external_nullifier: Vec<u8>,
+    /// When set, the circuit enforces a counter state transition between
+    /// `consumed_value` and `created_value`.
+    #[serde(default)]
+    pub is_counter: bool,
+    /// Value carried by the consumed resource (counter mode).
+    #[serde(default)]
+    pub consumed_value: u128,
+    /// Value carried by the created resource (counter mode).
+    #[serde(default)]
+    pub created_value: u128,
+    /// Amount added to or subtracted from the consumed value (counter mode).
+    #[serde(default)]
+    pub delta: u128,
+    /// Direction of the transition: `true` adds `delta`, `false` subtracts it.
+    #[serde(default)]
+    pub is_addition: bool,
+    /// The opposite side of the counter transition: the created resource when
+    /// this proof is for the consumed side, and vice versa. Carried so each
+    /// proof can re-derive the counterpart's action-tree leaf and bind its
+    /// value, tying the transition across both leaves.
+    #[serde(default)]
+    pub counter_counterpart: Option<Resource>,
+    /// Authentication path rooting `counter_counterpart`'s leaf to the same
+    /// action tree as this resource's leaf.
+    #[serde(default)]
+    pub counter_counterpart_path: Option<MerklePath>,
+    /// Nullifier key used to derive the counterpart's tag.
+    #[serde(default)]
+    pub counter_counterpart_nf_key: NullifierKey,
 }
 
 impl HelloWorldWitness {
@@ -27,6 +69,91 @@ impl HelloWorldWitness {
             hello_world,
             hello_world_existence_path,
             nf_key,
+            ciphertext: Vec::new(),
+            ephemeral_pubkey: Vec::new(),
+            external_nullifier: Vec::new(),
+            is_counter: false,
+            consumed_value: 0,
+            created_value: 0,
+            delta: 0,
+            is_addition: false,
+            counter_counterpart: None,
+            counter_counterpart_path: None,
+            counter_counterpart_nf_key: NullifierKey::default(),
+        }
+    }
+
+    /// Configures a counter state transition: the created resource carries the
+    /// consumed value adjusted by `delta` (added when `is_addition`, otherwise
+    /// subtracted). The circuit enforces the relation and rejects over/underflow.
+    pub fn with_counter_transition(
+        mut self,
+        consumed_value: u128,
+        created_value: u128,
+        delta: u128,
+        is_addition: bool,
+    ) -> Self {
+        self.is_counter = true;
+        self.consumed_value = consumed_value;
+        self.created_value = created_value;
+        self.delta = delta;
+        self.is_addition = is_addition;
+        self
+    }
+
+    /// Binds the counterpart side of a counter transition so the created and
+    /// consumed values are both tied to real leaves of the same action tree.
+    pub fn with_counter_counterpart(
+        mut self,
+        counterpart: Resource,
+        counterpart_path: MerklePath,
+        counterpart_nf_key: NullifierKey,
+    ) -> Self {
+        self.counter_counterpart = Some(counterpart);
+        self.counter_counterpart_path = Some(counterpart_path);
+        self.counter_counterpart_nf_key = counterpart_nf_key;
+        self
+    }
+
+    /// Attaches an encrypted note (ciphertext plus ephemeral public key) to the
+    /// witness so it is carried in the resource's `AppData` and bound into the
+    /// logic proof.
+    pub fn with_encrypted_note(
+        mut self,
+        ciphertext: Vec<u8>,
+        ephemeral_pubkey: Vec<u8>,
+    ) -> Self {
+        self.ciphertext = ciphertext;
+        self.ephemeral_pubkey = ephemeral_pubkey;
+        self
+    }
+
+    /// Scopes this witness to an external nullifier (domain/epoch/application
+    /// identifier) so the resource cannot be double-spent across contexts.
+    pub fn with_external_nullifier(mut self, external_nullifier: Vec<u8>) -> Self {
+        self.external_nullifier = external_nullifier;
+        self
+    }
+
+    /// Hashes the external-nullifier scope into a 32-byte value.
+    fn external_nullifier_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"ARM.external-nullifier");
+        hasher.update(&self.external_nullifier);
+        hasher.finalize().into()
+    }
+
+    /// Scopes a resource tag to this witness's external nullifier, matching the
+    /// scoped leaves the host builds into the action tree.
+    fn scope_tag(&self, tag: &Digest) -> Digest {
+        if self.external_nullifier.is_empty() {
+            tag.clone()
+        } else {
+            let mut hasher = Sha256::new();
+            hasher.update(tag.as_bytes());
+            hasher.update(self.external_nullifier_hash());
+            let scoped: [u8; 32] = hasher.finalize().into();
+            Digest::from_bytes(scoped)
         }
     }
 }
@@ -35,17 +162,475 @@ impl LogicCircuit for HelloWorldWitness {
     fn constrain(&self) -> LogicInstance {
         // Extract and validate label_ref from both resources
         let label: [u8; 11] = self.hello_world.label_ref[0..11].try_into().unwrap();
-        
+
         // Verify that the label contains "Hello World"
         let expected_label = b"Hello World";
         assert_eq!(&label, expected_label);
 
+        // Counter mode: enforce `created == consumed +/- delta` as a circuit
+        // constraint rather than trusting the host, rejecting over/underflow.
+        if self.is_counter {
+            let expected = if self.is_addition {
+                self.consumed_value
+                    .checked_add(self.delta)
+                    .expect("counter addition overflow")
+            } else {
+                self.consumed_value
+                    .checked_sub(self.delta)
+                    .expect("counter subtraction underflow")
+            };
+            assert_eq!(
+                self.created_value, expected,
+                "counter transition does not match created value"
+            );
+
+            // Bind this resource's own value_ref to the declared side of the
+            // transition so neither value can be forged.
+            let own = u128::from_le_bytes(self.hello_world.value_ref[0..16].try_into().unwrap());
+            let declared = if self.is_consumed {
+                self.consumed_value
+            } else {
+                self.created_value
+            };
+            assert_eq!(own, declared, "value_ref does not match declared counter value");
+        }
+
         let tag = self.hello_world.tag(self.is_consumed, &self.nf_key);
 
+        // Scope the tag to the external nullifier so the resource yields a
+        // distinct nullifier per application context.
+        let scoped_tag = self.scope_tag(&tag);
+
+        // Counter mode: bind the counterpart side of the transition across the
+        // two proofs. Recompute the counterpart's scoped leaf, prove it roots to
+        // the same action tree as this resource, and bind its value_ref. Without
+        // this the consumed-side and created-side proofs could declare different
+        // transitions; now consumed_value and created_value are each tied to a
+        // real, co-rooted leaf, so `created == consumed +/- delta` holds across
+        // the action rather than within one proof's free inputs.
+        if self.is_counter {
+            let counterpart = self
+                .counter_counterpart
+                .as_ref()
+                .expect("counter mode requires the counterpart resource");
+            let counterpart_path = self
+                .counter_counterpart_path
+                .as_ref()
+                .expect("counter mode requires the counterpart path");
+            let counterpart_tag = self.scope_tag(
+                &counterpart.tag(!self.is_consumed, &self.counter_counterpart_nf_key),
+            );
+            assert_eq!(
+                counterpart_path.root(&counterpart_tag),
+                self.hello_world_existence_path.root(&scoped_tag),
+                "counterpart is not a leaf of the same action tree"
+            );
+            let counterpart_value =
+                u128::from_le_bytes(counterpart.value_ref[0..16].try_into().unwrap());
+            let declared_counterpart = if self.is_consumed {
+                self.created_value
+            } else {
+                self.consumed_value
+            };
+            assert_eq!(
+                counterpart_value, declared_counterpart,
+                "counterpart value_ref does not match declared counter value"
+            );
+        }
+
+        // Commit the encrypted note into the logic instance. The proof fixes the
+        // exact ciphertext and ephemeral key alongside the resource's tag, so the
+        // delivered note is tamper-evident and tied to this proof. A well-formed
+        // note carries both parts together, with a 32-byte X25519 ephemeral key.
+        //
+        // Note: this binds the note *to the proof*, not to the resource's
+        // plaintext opening. Correspondence — that the ciphertext decrypts to
+        // this resource — cannot be checked in-circuit without the recipient's
+        // key, so it is verified receiver-side on decryption.
+        assert_eq!(
+            self.ciphertext.is_empty(),
+            self.ephemeral_pubkey.is_empty(),
+            "an encrypted note must carry both a ciphertext and an ephemeral key"
+        );
+        assert!(
+            self.ephemeral_pubkey.is_empty() || self.ephemeral_pubkey.len() == 32,
+            "ephemeral public key must be a 32-byte X25519 key"
+        );
+        let app_data = AppData {
+            ciphertext: self.ciphertext.clone(),
+            ephemeral_pubkey: self.ephemeral_pubkey.clone(),
+            ..Default::default()
+        };
+
+        LogicInstance {
+            tag: scoped_tag.as_words().to_vec(),
+            is_consumed: self.is_consumed,
+            root: self.hello_world_existence_path.root(&scoped_tag),
+            app_data,
+        }
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct TokenBalanceEntry {
+    /// Token denomination (`label_ref`) of the contributing resource.
+    pub denom: Vec<u8>,
+    /// Quantity carried in the first sixteen bytes of its `value_ref`.
+    pub quantity: u128,
+    /// Action-tree leaf for this resource: its nullifier when consumed, its
+    /// commitment when created.
+    pub tag: Digest,
+    /// Authentication path proving `tag` is a leaf of the same action tree.
+    pub path: MerklePath,
+}
+
+/// Witness for the quantity-conserving token resource logic.
+///
+/// Unlike [`HelloWorldWitness`], which checks a fixed label and, in counter
+/// mode, a `created == consumed +/- delta` transition, this witness treats the
+/// resource `label_ref` as a token denomination and the first sixteen bytes of
+/// `value_ref` as a little-endian `u128` quantity. The trailing sixteen bytes
+/// of `value_ref` carry the owner/authorization tag, which is bound through the
+/// resource commitment and read with [`TokenWitness::owner`]; it is not placed
+/// in the logic instance's `AppData`.
+///
+/// To enforce value conservation the witness also carries every consumed and
+/// created resource in the action as a [`TokenBalanceEntry`] — a
+/// `(denomination, quantity)` pair together with the action-tree leaf
+/// (nullifier or commitment) that resource contributes and a path rooting it to
+/// the same action tree. Binding each declared quantity to a real leaf stops a
+/// host from padding the balance with a phantom quantity that corresponds to no
+/// committed resource, so the circuit can restrict the balance to this
+/// resource's own denomination and assert that nothing of it is minted or
+/// burned out of thin air.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct TokenWitness {
+    pub is_consumed: bool,
+    pub token: Resource,
+    pub token_existence_path: MerklePath,
+    pub nf_key: NullifierKey,
+    /// Balance contribution of every consumed resource in the action.
+    pub consumed_balance: Vec<TokenBalanceEntry>,
+    /// Balance contribution of every created resource in the action.
+    pub created_balance: Vec<TokenBalanceEntry>,
+}
+
+impl TokenWitness {
+    pub fn new(
+        is_consumed: bool,
+        token: Resource,
+        token_existence_path: MerklePath,
+        nf_key: NullifierKey,
+        consumed_balance: Vec<TokenBalanceEntry>,
+        created_balance: Vec<TokenBalanceEntry>,
+    ) -> Self {
+        Self {
+            is_consumed,
+            token,
+            token_existence_path,
+            nf_key,
+            consumed_balance,
+            created_balance,
+        }
+    }
+
+    /// Decodes the little-endian `u128` quantity stored in the first sixteen
+    /// bytes of a token `value_ref`.
+    pub fn quantity(&self) -> u128 {
+        let bytes: [u8; 16] = self.token.value_ref[0..16].try_into().unwrap();
+        u128::from_le_bytes(bytes)
+    }
+
+    /// Returns the sixteen-byte owner/authorization tag stored in the trailing
+    /// half of the token `value_ref`.
+    pub fn owner(&self) -> [u8; 16] {
+        self.token.value_ref[16..32].try_into().unwrap()
+    }
+}
+
+impl LogicCircuit for TokenWitness {
+    fn constrain(&self) -> LogicInstance {
+        // The denomination identifier lives in `label_ref`; a non-empty
+        // denomination is required so the quantity is meaningfully scoped.
+        assert!(
+            self.token.label_ref.iter().any(|b| *b != 0),
+            "token denomination must not be empty"
+        );
+
+        // This resource's own action-tree leaf, and the root it proves against.
+        let tag = self.token.tag(self.is_consumed, &self.nf_key);
+        let root = self.token_existence_path.root(&tag);
+
+        // Bind every declared quantity to a real leaf of *this* action tree: each
+        // entry carries the nullifier/commitment it accounts for and a path that
+        // must root to the same tree. A phantom quantity would need a real leaf
+        // (backed by a compliance proof), so the host can no longer pad the
+        // balance with numbers that correspond to no committed resource.
+        for entry in self.consumed_balance.iter().chain(self.created_balance.iter()) {
+            assert_eq!(
+                entry.path.root(&entry.tag),
+                root,
+                "balance entry is not a leaf of this action tree"
+            );
+        }
+
+        // This resource's own leaf must appear on its side of the balance with
+        // its own denomination and quantity, so it cannot be summed under a
+        // different denomination nor dropped entirely.
+        let denom = self.token.label_ref.clone();
+        let quantity = self.quantity();
+        let own_side = if self.is_consumed {
+            &self.consumed_balance
+        } else {
+            &self.created_balance
+        };
+        assert!(
+            own_side
+                .iter()
+                .any(|e| e.tag == tag && e.denom == denom && e.quantity == quantity),
+            "resource leaf missing from the action's balance declaration"
+        );
+
+        // Value conservation, scoped to this resource's denomination: the sum of
+        // consumed quantities of `denom` must equal the sum of created ones.
+        // Other denominations balance under their own proofs. Checked folds keep
+        // the circuit total so the host cannot hide an imbalance.
+        let consumed_total: u128 = self
+            .consumed_balance
+            .iter()
+            .filter(|e| e.denom == denom)
+            .fold(0u128, |acc, e| acc.checked_add(e.quantity).expect("consumed overflow"));
+        let created_total: u128 = self
+            .created_balance
+            .iter()
+            .filter(|e| e.denom == denom)
+            .fold(0u128, |acc, e| acc.checked_add(e.quantity).expect("created overflow"));
+        assert_eq!(
+            consumed_total, created_total,
+            "token quantity is not conserved across the action"
+        );
+
+        // The owner/authorization tag lives in the trailing half of `value_ref`,
+        // which feeds the resource commitment and hence this proof's tag, so it is
+        // already bound to the resource. Do not repackage it into the note
+        // `ciphertext` field (reserved for the encrypted payload in
+        // [`HelloWorldWitness`]); decode it with [`TokenWitness::owner`] off the
+        // committed resource instead of re-surfacing it through `AppData`.
+        LogicInstance {
+            tag: tag.as_words().to_vec(),
+            is_consumed: self.is_consumed,
+            root: self.token_existence_path.root(&tag),
+            app_data: AppData { ..Default::default() },
+        }
+    }
+}
+
+/// Witness for the signature-authorized consumption logic.
+///
+/// The resource commits to an Ed25519 verifying key in the first thirty-two
+/// bytes of its `value_ref`. When the resource is consumed the witness must
+/// carry a signature, made by the matching signing key, over the resource's
+/// computed `tag`; the circuit rejects the proof unless the signature verifies.
+/// Creation (`is_consumed == false`) places no signature requirement, so anyone
+/// may hand the resource to the key holder, but only the key holder can spend it.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AuthWitness {
+    pub is_consumed: bool,
+    pub auth_resource: Resource,
+    pub auth_existence_path: MerklePath,
+    pub nf_key: NullifierKey,
+    /// Ed25519 signature over the resource tag (64 bytes: 32-byte R || 32-byte
+    /// s). Held as a `Vec<u8>` because serde only derives array impls up to
+    /// length 32.
+    pub signature: Vec<u8>,
+    /// Ed25519 verifying key asserted to match the one committed in `value_ref`.
+    pub verifying_key: [u8; 32],
+}
+
+impl Default for AuthWitness {
+    fn default() -> Self {
+        Self {
+            is_consumed: false,
+            auth_resource: Resource::default(),
+            auth_existence_path: MerklePath::default(),
+            nf_key: NullifierKey::default(),
+            signature: Vec::new(),
+            verifying_key: [0u8; 32],
+        }
+    }
+}
+
+impl AuthWitness {
+    pub fn new(
+        is_consumed: bool,
+        auth_resource: Resource,
+        auth_existence_path: MerklePath,
+        nf_key: NullifierKey,
+        signature: Vec<u8>,
+        verifying_key: [u8; 32],
+    ) -> Self {
+        Self {
+            is_consumed,
+            auth_resource,
+            auth_existence_path,
+            nf_key,
+            signature,
+            verifying_key,
+        }
+    }
+}
+
+impl LogicCircuit for AuthWitness {
+    fn constrain(&self) -> LogicInstance {
+        let tag = self.auth_resource.tag(self.is_consumed, &self.nf_key);
+
+        // Only consumption is gated. At spend time the verifying key must equal
+        // the one committed in `value_ref` (so the owner pinned at creation
+        // cannot be swapped) and must have signed the tag. A created resource's
+        // `value_ref` carries application state rather than a key, so neither
+        // check applies to the `is_consumed == false` side.
+        if self.is_consumed {
+            let committed_key: [u8; 32] = self.auth_resource.value_ref[0..32].try_into().unwrap();
+            assert_eq!(
+                committed_key, self.verifying_key,
+                "verifying key does not match the one committed in value_ref"
+            );
+
+            let key = VerifyingKey::from_bytes(&self.verifying_key)
+                .expect("committed verifying key is malformed");
+            let signature = Signature::try_from(self.signature.as_slice())
+                .expect("signature must be 64 bytes");
+            key.verify(tag.as_bytes(), &signature)
+                .expect("signature does not authorize consumption of this resource");
+        }
+
+        LogicInstance {
+            tag: tag.as_words().to_vec(),
+            is_consumed: self.is_consumed,
+            root: self.auth_existence_path.root(&tag),
+            app_data: AppData {..Default::default()},
+        }
+    }
+}
+
+/// Witness for the intent resource logic used by the solver.
+///
+/// An intent expresses "give `offered` of one kind, want `wanted` of another".
+/// When a solver consumes the intent it declares how much of the offered side
+/// it released (`filled_out`) and how much of the wanted side it delivered
+/// (`spent_in`). The circuit accepts the spend only if the intent is either
+/// fully satisfied, or partially filled with a matching remainder intent and a
+/// proportional release of the offered side.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct IntentWitness {
+    pub is_consumed: bool,
+    pub intent: Resource,
+    pub intent_existence_path: MerklePath,
+    pub nf_key: NullifierKey,
+    /// Quantity offered by the intent owner.
+    pub offered: u128,
+    /// Minimum quantity the intent owner wants in return.
+    pub wanted: u128,
+    /// Quantity of the offered side released by the solver.
+    pub filled_out: u128,
+    /// Quantity of the wanted side delivered to the owner.
+    pub spent_in: u128,
+    /// Whether a remainder intent was created for the unfilled part.
+    pub is_partial: bool,
+}
+
+impl IntentWitness {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        is_consumed: bool,
+        intent: Resource,
+        intent_existence_path: MerklePath,
+        nf_key: NullifierKey,
+        offered: u128,
+        wanted: u128,
+        filled_out: u128,
+        spent_in: u128,
+        is_partial: bool,
+    ) -> Self {
+        Self {
+            is_consumed,
+            intent,
+            intent_existence_path,
+            nf_key,
+            offered,
+            wanted,
+            filled_out,
+            spent_in,
+            is_partial,
+        }
+    }
+}
+
+impl LogicCircuit for IntentWitness {
+    fn constrain(&self) -> LogicInstance {
+        // Re-derive the swap terms from the intent's own `value_ref` so the
+        // proportionality is bound to what was actually committed rather than to
+        // free witness inputs. The layout mirrors the host-side `SwapTerms`:
+        // four little-endian u64 fields give_kind | give_quantity | want_kind |
+        // want_quantity; the offered/wanted quantities are the two quantity
+        // fields.
+        let give_quantity =
+            u64::from_le_bytes(self.intent.value_ref[8..16].try_into().unwrap()) as u128;
+        let want_quantity =
+            u64::from_le_bytes(self.intent.value_ref[24..32].try_into().unwrap()) as u128;
+
+        // The solver may never release more than offered; over-release would
+        // unbalance the merged transaction.
+        assert!(self.filled_out <= self.offered, "released more than offered");
+
+        if self.is_consumed {
+            // Consumed side carries the original intent: its committed terms must
+            // equal the offered/wanted the solve is proven against, so the ratio
+            // is anchored to the real intent resource.
+            assert_eq!(give_quantity, self.offered, "offered does not match committed intent");
+            assert_eq!(want_quantity, self.wanted, "wanted does not match committed intent");
+
+            if self.is_partial {
+                // Partial fulfillment: the released fraction of the offered side
+                // must equal the delivered fraction of the wanted side, i.e.
+                // filled_out / offered == spent_in / wanted, cross-multiplied to
+                // stay in integer arithmetic.
+                assert_eq!(
+                    self.filled_out.checked_mul(self.wanted),
+                    self.spent_in.checked_mul(self.offered),
+                    "partial fulfillment is not proportional"
+                );
+                // Something must actually be filled for a partial solve to count.
+                assert!(self.filled_out > 0, "partial fulfillment filled nothing");
+            } else {
+                // Full satisfaction: the owner gets at least what they wanted and
+                // the whole offered side is released.
+                assert!(self.spent_in >= self.wanted, "intent not fully satisfied");
+                assert_eq!(self.filled_out, self.offered, "offered side not fully released");
+            }
+        } else if self.is_partial {
+            // Created side carries the residual intent: its committed give/want
+            // must equal the original minus what the solver filled and spent,
+            // binding the filled/spent amounts to the created resource's
+            // `value_ref` instead of leaving them free.
+            let residual_give = self
+                .offered
+                .checked_sub(self.filled_out)
+                .expect("residual give underflow");
+            let residual_want = self
+                .wanted
+                .checked_sub(self.spent_in)
+                .expect("residual want underflow");
+            assert_eq!(give_quantity, residual_give, "residual give does not match unfilled offer");
+            assert_eq!(want_quantity, residual_want, "residual want does not match unfilled want");
+        }
+
+        let tag = self.intent.tag(self.is_consumed, &self.nf_key);
+
         LogicInstance {
             tag: tag.as_words().to_vec(),
             is_consumed: self.is_consumed,
-            root: self.hello_world_existence_path.root(&tag),
+            root: self.intent_existence_path.root(&tag),
             app_data: AppData {..Default::default()},
         }
     }